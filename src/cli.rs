@@ -0,0 +1,112 @@
+//! Command-line surface for `lfmc`.
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+pub struct Args {
+    /// Your Last.fm API Key
+    #[arg(short = 'k', long, env = "API_KEY", global = true)]
+    pub api_key: String,
+
+    /// Your Last.fm Username
+    #[arg(short, long, env = "USERNAME", global = true)]
+    pub username: String,
+
+    /// Path to the local SQLite scrobble archive, used by `sync` and `sql`
+    #[arg(long, default_value = "lfmc.db", env = "DB_PATH", global = true)]
+    pub db: String,
+
+    /// Output format for results
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Tweet, global = true)]
+    pub output: OutputFormat,
+
+    /// How many times to retry a request that fails transiently
+    #[arg(long, default_value = "3", global = true)]
+    pub max_retries: u32,
+
+    /// Maximum outgoing requests per second to the Last.fm API
+    #[arg(long, default_value = "5", global = true)]
+    pub rate_limit: f64,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum OutputFormat {
+    /// The original "♫ My Top N..." social-post text
+    Tweet,
+    Json,
+    Yaml,
+    Table,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Show your top artists
+    Artists {
+        /// The limit of artists
+        #[arg(short, long, default_value = "5", env = "LIMIT")]
+        limit: u16,
+
+        /// The lookback period
+        #[arg(short, long, default_value = "7day", env = "PERIOD")]
+        period: String,
+    },
+    /// Show your top tracks
+    Tracks {
+        /// The limit of tracks
+        #[arg(short, long, default_value = "5", env = "LIMIT")]
+        limit: u16,
+
+        /// The lookback period
+        #[arg(short, long, default_value = "7day", env = "PERIOD")]
+        period: String,
+    },
+    /// Show your top albums
+    Albums {
+        /// The limit of albums
+        #[arg(short, long, default_value = "5", env = "LIMIT")]
+        limit: u16,
+
+        /// The lookback period
+        #[arg(short, long, default_value = "7day", env = "PERIOD")]
+        period: String,
+    },
+    /// Show your recently scrobbled tracks
+    Recent {
+        /// The limit of tracks per page
+        #[arg(short, long, default_value = "5", env = "LIMIT")]
+        limit: u16,
+
+        /// Only include scrobbles at or after this UNIX timestamp
+        #[arg(long)]
+        from: Option<i64>,
+
+        /// Only include scrobbles at or before this UNIX timestamp
+        #[arg(long)]
+        to: Option<i64>,
+    },
+    /// Recommend new artists based on your top artists' similar artists
+    Recommend {
+        /// The number of recommendations to return
+        #[arg(short, long, default_value = "10", env = "LIMIT")]
+        limit: u16,
+
+        /// How many of your top artists to use as seeds
+        #[arg(long, default_value = "20")]
+        seeds: u16,
+
+        /// The lookback period for your top artists
+        #[arg(short, long, default_value = "overall", env = "PERIOD")]
+        period: String,
+    },
+    /// Sync your full scrobble history into the local SQLite archive
+    Sync,
+    /// Run a SELECT query against the local SQLite archive
+    Sql {
+        /// The SELECT statement to run
+        query: String,
+    },
+}
@@ -0,0 +1,211 @@
+//! A small typed wrapper around the Last.fm HTTP API.
+//!
+//! Each method builds the right query string for its `method=...` call and
+//! deserializes straight into the matching struct in [`crate::models`], so
+//! a malformed response is caught once by serde instead of via scattered
+//! `.ok_or(anyhow!(...))` calls at every call site. Requests are throttled
+//! by a [`RateLimiter`] and retried with exponential backoff on transient
+//! network/HTTP/Last.fm errors, see [`crate::http`].
+
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+
+use crate::error::{LastFmError, LastFmErrorBody};
+use crate::http::{backoff_delay, RateLimiter, RetryConfig};
+use crate::models::{
+    RecentTracksResponse, SimilarArtist, SimilarArtistsResponse, TopAlbumsResponse, TopArtist,
+    TopArtistsResponse, TopTrack, TopTracksResponse, Track,
+};
+
+const API_ROOT: &str = "http://ws.audioscrobbler.com/2.0/";
+
+pub struct LastFMClient {
+    client: Client,
+    api_key: String,
+    username: String,
+    limiter: RateLimiter,
+    retry: RetryConfig,
+}
+
+impl LastFMClient {
+    pub fn new(api_key: String, username: String, max_retries: u32, rate_limit_per_sec: f64) -> Self {
+        LastFMClient {
+            client: Client::new(),
+            api_key,
+            username,
+            limiter: RateLimiter::new(rate_limit_per_sec),
+            retry: RetryConfig {
+                max_retries,
+                ..RetryConfig::default()
+            },
+        }
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, method: &str, extra: &[(&str, &str)]) -> Result<T> {
+        let mut params: Vec<(&str, &str)> = vec![
+            ("method", method),
+            ("user", &self.username),
+            ("api_key", &self.api_key),
+            ("format", "json"),
+        ];
+        params.extend_from_slice(extra);
+
+        let mut attempt = 0;
+        loop {
+            self.limiter.wait().await;
+
+            match self.client.get(API_ROOT).query(&params).send().await {
+                Ok(resp) => {
+                    let status = resp.status();
+                    let body = resp.text().await?;
+
+                    if let Ok(value) = serde_json::from_str::<T>(&body) {
+                        return Ok(value);
+                    }
+
+                    if let Ok(err_body) = serde_json::from_str::<LastFmErrorBody>(&body) {
+                        let err = LastFmError {
+                            code: err_body.error,
+                            message: err_body.message,
+                        };
+                        if err.is_transient() && attempt < self.retry.max_retries {
+                            attempt += 1;
+                            tokio::time::sleep(backoff_delay(&self.retry, attempt)).await;
+                            continue;
+                        }
+                        return Err(err.into());
+                    }
+
+                    if status.is_server_error() && attempt < self.retry.max_retries {
+                        attempt += 1;
+                        tokio::time::sleep(backoff_delay(&self.retry, attempt)).await;
+                        continue;
+                    }
+
+                    return Err(anyhow!(
+                        "Could not parse Last.fm response (status {}): {}",
+                        status,
+                        body
+                    ));
+                }
+                Err(err) => {
+                    if attempt < self.retry.max_retries {
+                        attempt += 1;
+                        tokio::time::sleep(backoff_delay(&self.retry, attempt)).await;
+                        continue;
+                    }
+                    return Err(err.into());
+                }
+            }
+        }
+    }
+
+    pub async fn top_artists(&self, period: &str, limit: u16) -> Result<Vec<TopArtist>> {
+        let limit_str = limit.to_string();
+        let resp: TopArtistsResponse = self
+            .get(
+                "user.gettopartists",
+                &[("period", period), ("limit", &limit_str)],
+            )
+            .await?;
+        Ok(resp.topartists.artist)
+    }
+
+    pub async fn top_tracks(&self, period: &str, limit: u16) -> Result<Vec<TopTrack>> {
+        let limit_str = limit.to_string();
+        let resp: TopTracksResponse = self
+            .get(
+                "user.gettoptracks",
+                &[("period", period), ("limit", &limit_str)],
+            )
+            .await?;
+        Ok(resp.toptracks.track)
+    }
+
+    pub async fn top_albums(&self, period: &str, limit: u16) -> Result<Vec<crate::models::TopAlbum>> {
+        let limit_str = limit.to_string();
+        let resp: TopAlbumsResponse = self
+            .get(
+                "user.gettopalbums",
+                &[("period", period), ("limit", &limit_str)],
+            )
+            .await?;
+        Ok(resp.topalbums.album)
+    }
+
+    pub async fn similar_artists(&self, artist: &str) -> Result<Vec<SimilarArtist>> {
+        let resp: SimilarArtistsResponse = self
+            .get(
+                "artist.getSimilar",
+                &[("artist", artist), ("autocorrect", "1")],
+            )
+            .await?;
+        Ok(resp.similarartists.artist)
+    }
+
+    async fn recent_tracks_page(
+        &self,
+        page: u32,
+        limit: u16,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<(Vec<Track>, u32)> {
+        let page_str = page.to_string();
+        let limit_str = limit.to_string();
+        let from_str = from.map(|v| v.to_string());
+        let to_str = to.map(|v| v.to_string());
+
+        let mut extra: Vec<(&str, &str)> = vec![("page", &page_str), ("limit", &limit_str)];
+        if let Some(ref from_str) = from_str {
+            extra.push(("from", from_str));
+        }
+        if let Some(ref to_str) = to_str {
+            extra.push(("to", to_str));
+        }
+
+        let resp: RecentTracksResponse = self.get("user.getrecenttracks", &extra).await?;
+        let total_pages: u32 = resp.recenttracks.attr.total_pages.parse().unwrap_or(1);
+        Ok((resp.recenttracks.track, total_pages))
+    }
+
+    /// Walk the user's full scrobble history, one page at a time, stopping
+    /// once scrobbles older than `from` are reached (tracks come back
+    /// newest-first) or the last page is exhausted.
+    pub async fn recent_tracks(
+        &self,
+        limit: u16,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<Vec<Track>> {
+        let mut page = 1;
+        let mut tracks = Vec::new();
+
+        loop {
+            let (page_tracks, total_pages) =
+                self.recent_tracks_page(page, limit, from, to).await?;
+            if page_tracks.is_empty() {
+                break;
+            }
+
+            let mut hit_cutoff = false;
+            for track in page_tracks {
+                if let (Some(from), Some(date)) = (from, &track.date) {
+                    if let Ok(uts) = date.uts.parse::<i64>() {
+                        if uts < from {
+                            hit_cutoff = true;
+                            break;
+                        }
+                    }
+                }
+                tracks.push(track);
+            }
+
+            if hit_cutoff || page >= total_pages {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(tracks)
+    }
+}
@@ -0,0 +1,123 @@
+//! Local SQLite archive of a user's scrobbles.
+//!
+//! `sync` pulls new scrobbles (via [`crate::client::LastFMClient::recent_tracks`])
+//! into a `scrobbles` table and records the timestamp of the newest one in
+//! `sync_state`, so later syncs only fetch what's new instead of re-walking
+//! the user's entire history.
+
+use anyhow::{anyhow, Result};
+use rusqlite::types::ValueRef;
+use rusqlite::{params, Connection};
+
+use crate::models::Track;
+
+const LAST_SYNCED_KEY: &str = "last_synced";
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: &str) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS scrobbles (
+                id        INTEGER PRIMARY KEY AUTOINCREMENT,
+                artist    TEXT NOT NULL,
+                album     TEXT NOT NULL,
+                track     TEXT NOT NULL,
+                timestamp INTEGER NOT NULL UNIQUE
+            );
+            CREATE TABLE IF NOT EXISTS sync_state (
+                key   TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );",
+        )?;
+        Ok(Store { conn })
+    }
+
+    pub fn last_synced(&self) -> Result<Option<i64>> {
+        let value: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT value FROM sync_state WHERE key = ?1",
+                params![LAST_SYNCED_KEY],
+                |row| row.get(0),
+            )
+            .ok();
+        Ok(value.and_then(|v| v.parse().ok()))
+    }
+
+    fn set_last_synced(&self, timestamp: i64) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO sync_state (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![LAST_SYNCED_KEY, timestamp.to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// Insert the given scrobbles (skipping ones already stored by
+    /// timestamp) and advance `last_synced` to the newest one seen.
+    pub fn store_tracks(&self, tracks: &[Track]) -> Result<usize> {
+        let mut inserted = 0;
+        let mut newest: Option<i64> = None;
+
+        for track in tracks {
+            let Some(date) = &track.date else {
+                continue;
+            };
+            let Ok(timestamp) = date.uts.parse::<i64>() else {
+                continue;
+            };
+
+            let changed = self.conn.execute(
+                "INSERT OR IGNORE INTO scrobbles (artist, album, track, timestamp)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![track.artist.name, track.album.name, track.name, timestamp],
+            )?;
+            inserted += changed;
+            newest = Some(newest.map_or(timestamp, |n: i64| n.max(timestamp)));
+        }
+
+        if let Some(newest) = newest {
+            self.set_last_synced(newest)?;
+        }
+
+        Ok(inserted)
+    }
+
+    /// Run a user-supplied read-only `SELECT` and return the column names
+    /// alongside the stringified rows. Rejects anything else so `sql`
+    /// can't be used to mutate or drop the archive.
+    pub fn query(&self, sql: &str) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        if !sql.trim_start().get(0..6).is_some_and(|s| s.eq_ignore_ascii_case("select")) {
+            return Err(anyhow!("Only SELECT statements are allowed."));
+        }
+
+        let mut stmt = self.conn.prepare(sql)?;
+        let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+
+        let rows = stmt.query_map([], |row| {
+            (0..columns.len())
+                .map(|i| row.get_ref(i).map(value_to_string))
+                .collect::<rusqlite::Result<Vec<String>>>()
+        })?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok((columns, out))
+    }
+}
+
+fn value_to_string(value: ValueRef<'_>) -> String {
+    match value {
+        ValueRef::Null => String::new(),
+        ValueRef::Integer(i) => i.to_string(),
+        ValueRef::Real(f) => f.to_string(),
+        ValueRef::Text(t) => String::from_utf8_lossy(t).into_owned(),
+        ValueRef::Blob(b) => format!("<{} byte blob>", b.len()),
+    }
+}
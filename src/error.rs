@@ -0,0 +1,34 @@
+//! Typed representation of the Last.fm API's own error envelope
+//! (`{"error": <code>, "message": "..."}`), so callers can match on a
+//! specific failure instead of a generic "bad response" string.
+
+use serde::Deserialize;
+use std::fmt;
+
+#[derive(Debug, Deserialize)]
+pub struct LastFmErrorBody {
+    pub error: u32,
+    pub message: String,
+}
+
+#[derive(Debug)]
+pub struct LastFmError {
+    pub code: u32,
+    pub message: String,
+}
+
+impl LastFmError {
+    /// Error codes Last.fm documents as transient (rate limiting, temporary
+    /// service trouble) and therefore worth retrying.
+    pub fn is_transient(&self) -> bool {
+        matches!(self.code, 11 | 16 | 29)
+    }
+}
+
+impl fmt::Display for LastFmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Last.fm API error {}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for LastFmError {}
@@ -0,0 +1,106 @@
+//! Client-side rate limiting and retry backoff for the Last.fm HTTP layer.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+use tokio::time::{sleep, Instant};
+
+pub struct RetryConfig {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        RetryConfig {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+/// `base * 2^attempt`, capped at `max_delay`, plus a little jitter so a
+/// burst of retrying clients don't all wake up at the same instant.
+pub fn backoff_delay(config: &RetryConfig, attempt: u32) -> Duration {
+    let exp_millis = config
+        .base_delay
+        .as_millis()
+        .saturating_mul(1u128 << attempt.min(32));
+    let capped = exp_millis.min(config.max_delay.as_millis());
+    let jitter = jitter_millis(capped as u64 / 4 + 1);
+    Duration::from_millis(capped as u64 + jitter)
+}
+
+fn jitter_millis(max: u64) -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0) as u64;
+    nanos % max.max(1)
+}
+
+/// Throttles outgoing requests to at most `per_second` requests per second.
+pub struct RateLimiter {
+    min_interval: Duration,
+    last: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(per_second: f64) -> Self {
+        RateLimiter {
+            min_interval: Duration::from_secs_f64(1.0 / per_second.max(0.001)),
+            last: Mutex::new(None),
+        }
+    }
+
+    pub async fn wait(&self) {
+        let mut last = self.last.lock().await;
+        if let Some(previous) = *last {
+            let elapsed = previous.elapsed();
+            if elapsed < self.min_interval {
+                sleep(self.min_interval - elapsed).await;
+            }
+        }
+        *last = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(base_ms: u64, max_ms: u64) -> RetryConfig {
+        RetryConfig {
+            max_retries: 5,
+            base_delay: Duration::from_millis(base_ms),
+            max_delay: Duration::from_millis(max_ms),
+        }
+    }
+
+    #[test]
+    fn backoff_delay_grows_exponentially_before_the_cap() {
+        let cfg = config(100, 10_000);
+        let delay = backoff_delay(&cfg, 2); // 100 * 2^2 = 400ms, plus jitter
+        assert!(delay.as_millis() >= 400);
+    }
+
+    #[test]
+    fn backoff_delay_caps_at_max_delay_plus_jitter() {
+        let cfg = config(1_000, 2_000);
+        let delay = backoff_delay(&cfg, 10); // uncapped this would be ~1_024_000ms
+        let max_millis = cfg.max_delay.as_millis() as u64;
+        assert!(delay.as_millis() as u64 >= max_millis);
+        assert!(delay.as_millis() as u64 <= max_millis + max_millis / 4 + 1);
+    }
+
+    #[tokio::test]
+    async fn rate_limiter_throttles_to_the_configured_rate() {
+        let limiter = RateLimiter::new(20.0); // one request per 50ms
+        let start = std::time::Instant::now();
+        limiter.wait().await;
+        limiter.wait().await;
+        limiter.wait().await;
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+}
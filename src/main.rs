@@ -1,176 +1,161 @@
+mod cli;
+mod client;
+mod db;
+mod error;
+mod http;
+mod models;
+mod output;
+mod recommend;
+
 use anyhow::{anyhow, Result};
 use chrono::Local;
 use clap::Parser;
-use dotenv;
 use fern::{log_file, Dispatch};
-use log::{debug, error, trace, LevelFilter};
-use reqwest;
-use serde_json::Value;
+use log::{debug, LevelFilter};
 use std::io::stdout;
 
-#[derive(Parser)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Your Last.fm API Key
-    #[arg(short = 'k', long, env = "API_KEY")]
-    api_key: String,
-
-    /// Your Last.fm Username
-    #[arg(short, long, env = "USERNAME")]
-    username: String,
-
-    /// The limit of Artists
-    #[arg(short, long, default_value = "5", env = "LIMIT")]
-    limit: u16,
-
-    /// The lookback period
-    #[arg(short, long, default_value = "7day", env = "PERIOD")]
-    period: String,
-}
-
-struct Config {
-    api_key: String,
-    username: String,
-    limit: u16,
-    period: String,
-}
-
-impl Config {
-    fn new(api_key: String, username: String, limit: u16, period: String) -> Self {
-        Config {
-            api_key,
-            username,
-            limit,
-            period,
-        }
+use cli::{Args, Command};
+use client::LastFMClient;
+use db::Store;
+use models::{Track, TopAlbum, TopArtist, TopTrack};
+use recommend::recommend;
+
+fn period_suffix(period: &str) -> Result<&'static str> {
+    match period {
+        "overall" => Ok(""),
+        "7day" => Ok(" week"),
+        "1month" => Ok(" month"),
+        "3month" => Ok(" 3 months"),
+        "6month" => Ok(" 6 months"),
+        "12month" => Ok(" year"),
+        _ => Err(anyhow!(
+            "Period {} not allowed. Only allow \"overall\", \"7day\", \"1month\", \"3month\", \"6month\", or \"12month\".",
+            period
+        )),
     }
+}
 
-    fn get_uri(&self) -> String {
-        format!(
-            "http://ws.audioscrobbler.com/{}/?method={}&user={}&api_key={}&format={}&period={}&limit={}",
-            "2.0",
-            "user.gettopartists",
-            &self.username,
-            &self.api_key,
-            "json",
-            &self.period,
-            &self.limit,
-        )
+fn join_with_ampersand(entries: Vec<String>) -> String {
+    let mut output = String::new();
+    let last = entries.len().saturating_sub(1);
+    for (i, entry) in entries.iter().enumerate() {
+        let ending = match i {
+            x if x == last => "",
+            x if x == last - 1 => ", &",
+            _ => ",",
+        };
+        output = format!("{} {}{}", output, entry, ending);
     }
+    output
 }
 
-fn construct_output(config: Config, json: Value) -> Result<String> {
-    let period: &str = match config.period.as_str() {
-        "overall" => "",
-        "7day" => " week",
-        "1month" => " month",
-        "3month" => " 3 months",
-        "6month" => " 6 months",
-        "12month" => " year",
-        _ => return Err(anyhow!("Period {} not allowed. Only allow \"overall\", \"7day\", \"1month\", \"3month\", \"6month\", or \"12month\".", config.period))
-    };
-    trace!("period={}", period);
-
-    let mut output: String = format!(
+fn format_artists(artists: &[TopArtist], limit: u16, period: &str) -> Result<String> {
+    let suffix = period_suffix(period)?;
+    let header = format!(
         "♫ My Top {} played artists in the past{} via #LastFM ♫:\n",
-        config.limit.to_string(),
-        period
+        limit, suffix
     );
-    trace!("output={}", output);
-
-    let artists = json["topartists"]["artist"]
-        .as_array()
-        .ok_or(anyhow!("Error parsing JSON."))?;
-
-    for (i, artist) in artists.iter().enumerate() {
-        trace!("i={},artist={}", i, artist);
-        let ending = match i {
-            x if x <= (config.limit as usize - 3) => ",",
-            x if x == (config.limit as usize - 2) => ", &",
-            _ => "",
-        };
+    let entries = artists
+        .iter()
+        .map(|a| format!("{} ({})", a.name, a.playcount))
+        .collect();
+    Ok(format!("{}{}.", header, join_with_ampersand(entries)))
+}
 
-        let name = artist["name"]
-            .as_str()
-            .ok_or(anyhow!("Artist not found."))?;
-        let playcount = artist["playcount"]
-            .as_str()
-            .ok_or(anyhow!("Playcount not found."))?;
+fn format_tracks(tracks: &[TopTrack], limit: u16, period: &str) -> Result<String> {
+    let suffix = period_suffix(period)?;
+    let header = format!(
+        "♫ My Top {} played tracks in the past{} via #LastFM ♫:\n",
+        limit, suffix
+    );
+    let entries = tracks
+        .iter()
+        .map(|t| format!("{} ({})", t.name, t.playcount))
+        .collect();
+    Ok(format!("{}{}.", header, join_with_ampersand(entries)))
+}
 
-        output = format!(" {} {} ({}){}", output, name, playcount, ending);
-        trace!("output={}", output);
-    }
+fn format_albums(albums: &[TopAlbum], limit: u16, period: &str) -> Result<String> {
+    let suffix = period_suffix(period)?;
+    let header = format!(
+        "♫ My Top {} played albums in the past{} via #LastFM ♫:\n",
+        limit, suffix
+    );
+    let entries = albums
+        .iter()
+        .map(|a| format!("{} by {} ({})", a.name, a.artist.name, a.playcount))
+        .collect();
+    Ok(format!("{}{}.", header, join_with_ampersand(entries)))
+}
 
-    trace!("output={}", output);
-    Ok(format!("{}.", output))
+fn format_recent(tracks: &[Track]) -> Result<String> {
+    let header = format!(
+        "♫ My {} most recent scrobbles via #LastFM ♫:\n",
+        tracks.len()
+    );
+    let entries = tracks
+        .iter()
+        .map(|t| format!("{} - {}", t.artist.name, t.name))
+        .collect();
+    Ok(format!("{}{}.", header, join_with_ampersand(entries)))
 }
 
 #[cfg(test)]
 mod tests {
-    use serde_json::Value;
+    use crate::{format_artists, period_suffix};
+    use crate::models::TopArtist;
 
-    use crate::{construct_output, Config};
     #[test]
-    fn test_config() {
-        let api_key = "api_key";
-        let username = "username";
-        let limit = 5;
-        let period = "7day";
-
-        let config = Config::new(
-            String::from(api_key),
-            String::from(username),
-            limit,
-            String::from(period),
-        );
-
-        let uri = config.get_uri();
-
-        let keys = [
-            format!("user={}", username),
-            format!("api_key={}", api_key),
-            format!("limit={}", limit),
-            format!("period={}", period),
-        ];
-        for pat in keys.iter() {
-            assert!(uri.find(pat).is_some());
-        }
+    fn test_period_suffix() {
+        assert_eq!(period_suffix("7day").unwrap(), " week");
+        assert!(period_suffix("bogus").is_err());
     }
 
     #[test]
-    fn test_construct_output() {
-        let api_key = "api_key";
-        let username = "username";
-        let limit = 5;
-        let period = "7day";
-
-        let config = Config::new(
-            String::from(api_key),
-            String::from(username),
-            limit,
-            String::from(period),
-        );
-
-        let artist = r#"
-        {
-            "topartists":{
-                "artist":["Fia","Sea","Tha","Foa","Fia"]}
-        }
-        "#;
+    fn test_format_artists() {
+        let artists = vec![
+            TopArtist {
+                name: "Fia".into(),
+                playcount: "10".into(),
+            },
+            TopArtist {
+                name: "Sea".into(),
+                playcount: "5".into(),
+            },
+        ];
+
+        let output = format_artists(&artists, 2, "7day").unwrap();
+        assert!(output.contains("Fia"));
+        assert!(output.contains("Sea"));
+    }
+}
+
+fn format_recommendations(recs: &[recommend::Recommendation]) -> String {
+    let header = "♫ Artists you might like via #LastFM ♫:\n".to_string();
+    let entries = recs
+        .iter()
+        .map(|r| format!("{} ({:.2})", r.name, r.score))
+        .collect();
+    format!("{}{}.", header, join_with_ampersand(entries))
+}
 
-        let parsed_json: Result<Value, serde_json::Error> = serde_json::from_str(artist);
+#[derive(serde::Serialize)]
+struct SyncResult {
+    inserted: usize,
+}
 
-        if let Ok(json) = parsed_json {
-            let output: Result<String, anyhow::Error> = construct_output(config, json);
-            if let Ok(output_string) = output {
-                let key = "Fia";
-                assert!(output_string.find(key).is_some());
-            }
-        }
+fn format_table(columns: &[String], rows: &[Vec<String>]) -> String {
+    let mut output = columns.join(" | ");
+    output.push('\n');
+    for row in rows {
+        output.push_str(&row.join(" | "));
+        output.push('\n');
     }
+    output
 }
 
-fn main() -> Result<()> {
+#[tokio::main]
+async fn main() -> Result<()> {
     let file_config = Dispatch::new()
         .format(move |out, message, record| {
             out.finish(format_args!(
@@ -197,7 +182,6 @@ fn main() -> Result<()> {
             ))
         })
         .level(LevelFilter::Warn)
-        // .chain(fern::log_file("lfmc.log").unwrap())
         .chain(stdout());
 
     Dispatch::new()
@@ -215,19 +199,78 @@ fn main() -> Result<()> {
     debug!("Parsing args ...");
     let args = Args::parse();
 
-    debug!("Creating config ...");
-    let config = Config::new(args.api_key, args.username, args.limit, args.period);
-
-    let resp: Result<_, reqwest::Error> = reqwest::blocking::get(config.get_uri())?.json::<Value>();
+    let client = LastFMClient::new(
+        args.api_key,
+        args.username,
+        args.max_retries,
+        args.rate_limit,
+    );
+    let output_format = args.output;
+
+    debug!("Running command ...");
+    let output = match args.command {
+        Command::Artists { limit, period } => {
+            let artists = client.top_artists(&period, limit).await?;
+            output::render(output_format, &artists, |a| {
+                format_artists(a, limit, &period)
+            })?
+        }
+        Command::Tracks { limit, period } => {
+            let tracks = client.top_tracks(&period, limit).await?;
+            output::render(output_format, &tracks, |t| format_tracks(t, limit, &period))?
+        }
+        Command::Albums { limit, period } => {
+            let albums = client.top_albums(&period, limit).await?;
+            output::render(output_format, &albums, |a| format_albums(a, limit, &period))?
+        }
+        Command::Recent { limit, from, to } => {
+            let tracks = client.recent_tracks(limit, from, to).await?;
+            output::render(output_format, &tracks, |t| format_recent(t))?
+        }
+        Command::Recommend { limit, seeds, period } => {
+            let top_artists = client.top_artists(&period, seeds).await?;
+            let recs = recommend(&top_artists, limit as usize, |artist| {
+                let client = &client;
+                async move { client.similar_artists(&artist).await }
+            })
+            .await?;
+            output::render(output_format, &recs, |r| Ok(format_recommendations(r)))?
+        }
+        Command::Sync => {
+            let store = Store::open(&args.db)?;
+            let since = store.last_synced();
+            debug!("Syncing scrobbles since {:?} ...", since);
+
+            let tracks = client
+                .recent_tracks(200, since.ok().flatten(), None)
+                .await?;
+            let inserted = store.store_tracks(&tracks)?;
+            let result = SyncResult { inserted };
+            output::render(output_format, &result, |r| {
+                Ok(format!("Synced {} new scrobble(s).", r.inserted))
+            })?
+        }
+        Command::Sql { query } => {
+            let store = Store::open(&args.db)?;
+            let (columns, rows) = store.query(&query)?;
+            let records: Vec<serde_json::Value> = rows
+                .iter()
+                .map(|row| {
+                    let map: serde_json::Map<String, serde_json::Value> = columns
+                        .iter()
+                        .cloned()
+                        .zip(row.iter().cloned().map(serde_json::Value::String))
+                        .collect();
+                    serde_json::Value::Object(map)
+                })
+                .collect();
+            output::render(output_format, &records, |_| {
+                Ok(format_table(&columns, &rows))
+            })?
+        }
+    };
 
-    if let Ok(json) = resp {
-        debug!("Constructing output ...");
-        let output = construct_output(config, json)?;
-        println!("\n{}\n", output);
-    } else {
-        error!("Could not convert response to JSON.");
-        return Err(anyhow!("Could not convert response to JSON."));
-    }
+    println!("\n{}\n", output);
 
     debug!("main finished.");
     Ok(())
@@ -0,0 +1,118 @@
+//! Typed representations of the Last.fm API responses we care about.
+//!
+//! The raw API wraps every payload in a method-specific envelope
+//! (`topartists`, `toptracks`, ...), so each envelope gets its own struct
+//! with a field named after that key instead of us poking at a bare
+//! `serde_json::Value` at the call site.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TopArtist {
+    pub name: String,
+    pub playcount: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TopArtistsResponse {
+    pub topartists: TopArtists,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TopArtists {
+    pub artist: Vec<TopArtist>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TopTrack {
+    pub name: String,
+    pub playcount: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TopTracksResponse {
+    pub toptracks: TopTracks,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TopTracks {
+    pub track: Vec<TopTrack>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TopAlbum {
+    pub name: String,
+    pub playcount: String,
+    pub artist: ArtistRef,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ArtistRef {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TopAlbumsResponse {
+    pub topalbums: TopAlbums,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TopAlbums {
+    pub album: Vec<TopAlbum>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Track {
+    pub artist: ArtistRef,
+    pub album: AlbumRef,
+    pub name: String,
+    #[serde(default)]
+    pub date: Option<TrackDate>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AlbumRef {
+    #[serde(rename = "#text")]
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TrackDate {
+    pub uts: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecentTracksResponse {
+    pub recenttracks: RecentTracks,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecentTracks {
+    pub track: Vec<Track>,
+    #[serde(rename = "@attr")]
+    pub attr: RecentTracksAttr,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecentTracksAttr {
+    #[serde(rename = "totalPages")]
+    pub total_pages: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SimilarArtist {
+    pub name: String,
+    #[serde(rename = "match")]
+    pub match_score: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SimilarArtistsResponse {
+    pub similarartists: SimilarArtists,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SimilarArtists {
+    #[serde(default)]
+    pub artist: Vec<SimilarArtist>,
+}
@@ -0,0 +1,78 @@
+//! Pluggable rendering of structured results: the default social-post
+//! "tweet" text, or machine-readable JSON/YAML/table for scripting.
+//!
+//! YAML support follows the optional-feature pattern used by tools like
+//! rustypipe's `report-yaml`: it's gated behind this crate's own
+//! `serde_yaml` cargo feature so a default build doesn't pull in the
+//! `serde_yaml` crate.
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::cli::OutputFormat;
+
+pub fn render<T, F>(format: OutputFormat, data: &T, tweet: F) -> Result<String>
+where
+    T: Serialize,
+    F: FnOnce(&T) -> Result<String>,
+{
+    match format {
+        OutputFormat::Tweet => tweet(data),
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(data)?),
+        OutputFormat::Yaml => yaml::render(data),
+        OutputFormat::Table => {
+            let value = serde_json::to_value(data)?;
+            Ok(render_table(&value))
+        }
+    }
+}
+
+fn render_table(value: &serde_json::Value) -> String {
+    let Some(items) = value.as_array() else {
+        return value.to_string();
+    };
+    let Some(columns) = items.first().and_then(|v| v.as_object()) else {
+        return String::new();
+    };
+    let columns: Vec<String> = columns.keys().cloned().collect();
+
+    let mut output = columns.join(" | ");
+    output.push('\n');
+    for item in items {
+        let Some(obj) = item.as_object() else { continue };
+        let row: Vec<String> = columns
+            .iter()
+            .map(|c| obj.get(c).map(cell_to_string).unwrap_or_default())
+            .collect();
+        output.push_str(&row.join(" | "));
+        output.push('\n');
+    }
+    output
+}
+
+fn cell_to_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(feature = "serde_yaml")]
+mod yaml {
+    use super::*;
+
+    pub fn render<T: Serialize>(data: &T) -> Result<String> {
+        Ok(serde_yaml::to_string(data)?)
+    }
+}
+
+#[cfg(not(feature = "serde_yaml"))]
+mod yaml {
+    use super::*;
+
+    pub fn render<T: Serialize>(_data: &T) -> Result<String> {
+        Err(anyhow::anyhow!(
+            "YAML output requires building lfmc with the `serde_yaml` feature"
+        ))
+    }
+}
@@ -0,0 +1,147 @@
+//! Artist recommendations from similar-artist aggregation over listening weights.
+//!
+//! For each of the user's top artists (weight `w_i`, their playcount
+//! normalized by the max so no single heavily-played artist dominates), we
+//! fetch `artist.getSimilar` and accumulate `score(c) = Σ w_i * match(i, c)`
+//! over every seed that lists candidate `c`. Candidates already in the
+//! user's top-artists set are dropped, and names are deduped case-insensitively.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+
+use crate::models::{SimilarArtist, TopArtist};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Recommendation {
+    pub name: String,
+    pub score: f64,
+}
+
+pub async fn recommend<F, Fut>(
+    seeds: &[TopArtist],
+    limit: usize,
+    mut fetch_similar: F,
+) -> Result<Vec<Recommendation>>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<Vec<SimilarArtist>>>,
+{
+    let seed_names: std::collections::HashSet<String> =
+        seeds.iter().map(|a| a.name.to_lowercase()).collect();
+
+    let max_playcount = seeds
+        .iter()
+        .filter_map(|a| a.playcount.parse::<f64>().ok())
+        .fold(0.0_f64, f64::max);
+
+    let mut scores: HashMap<String, (String, f64)> = HashMap::new();
+
+    for seed in seeds {
+        let Ok(playcount) = seed.playcount.parse::<f64>() else {
+            continue;
+        };
+        let weight = if max_playcount > 0.0 {
+            playcount / max_playcount
+        } else {
+            0.0
+        };
+
+        let similar = fetch_similar(seed.name.clone()).await?;
+        if similar.is_empty() {
+            continue;
+        }
+
+        for candidate in similar {
+            let key = candidate.name.to_lowercase();
+            if seed_names.contains(&key) {
+                continue;
+            }
+            let Ok(match_score) = candidate.match_score.parse::<f64>() else {
+                continue;
+            };
+
+            let entry = scores
+                .entry(key)
+                .or_insert_with(|| (candidate.name.clone(), 0.0));
+            entry.1 += weight * match_score;
+        }
+    }
+
+    let mut recommendations: Vec<Recommendation> = scores
+        .into_values()
+        .map(|(name, score)| Recommendation { name, score })
+        .collect();
+    recommendations.sort_by(|a, b| b.score.total_cmp(&a.score));
+    recommendations.truncate(limit);
+
+    Ok(recommendations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn artist(name: &str, playcount: &str) -> TopArtist {
+        TopArtist {
+            name: name.to_string(),
+            playcount: playcount.to_string(),
+        }
+    }
+
+    fn similar(name: &str, m: &str) -> SimilarArtist {
+        SimilarArtist {
+            name: name.to_string(),
+            match_score: m.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn aggregates_and_dedupes_candidates() {
+        let seeds = vec![artist("A", "100"), artist("B", "50")];
+
+        let recs = recommend(&seeds, 10, |name| async move {
+            Ok(match name.as_str() {
+                "A" => vec![similar("C", "1.0"), similar("D", "0.5")],
+                "B" => vec![similar("c", "0.5"), similar("A", "0.9")],
+                _ => vec![],
+            })
+        })
+        .await
+        .unwrap();
+
+        // "A" is a seed, so it must never appear as a recommendation.
+        assert!(recs.iter().all(|r| r.name.to_lowercase() != "a"));
+
+        let c = recs.iter().find(|r| r.name.to_lowercase() == "c").unwrap();
+        // score(C) = 1.0 * 1.0 (seed A, full weight) + 0.5 * 0.5 (seed B, half weight)
+        assert!((c.score - 1.25).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn skips_seeds_with_no_similar_artists() {
+        let seeds = vec![artist("A", "100")];
+        let recs = recommend(&seeds, 10, |_| async { Ok(vec![]) })
+            .await
+            .unwrap();
+        assert!(recs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn does_not_panic_on_nan_scores() {
+        let seeds = vec![artist("A", "100"), artist("B", "50")];
+
+        let recs = recommend(&seeds, 10, |name| async move {
+            Ok(match name.as_str() {
+                "A" => vec![similar("C", "nan")],
+                "B" => vec![similar("D", "0.5")],
+                _ => vec![],
+            })
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(recs.len(), 2);
+    }
+}